@@ -0,0 +1,113 @@
+// Serial against parallel history analysis over a synthetic `git log`.
+//
+// History analysis lives in the binary, so its source is compiled as a module
+// here and the benchmark lives inside that module to reach its private items.
+
+use criterion::{criterion_group, criterion_main};
+
+#[allow(dead_code)]
+mod commitkit {
+    include!("../commitkit.rs");
+
+    pub mod bench {
+        use super::*;
+        use criterion::{BenchmarkId, Criterion, Throughput};
+
+        const TYPES: [&str; 5] = ["feat", "fix", "docs", "refactor", "chore"];
+
+        // Records in the format cached_history_log reads from git.
+        fn synthetic_log(commits: usize) -> String {
+            let mut log = String::new();
+            for i in 0..commits {
+                log.push_str(&format!(
+                    "\x1e{:040x}\x1fDev {}\x1fdev{}@example.com\x1f2024-{:02}\x1f\
+                     {}(core): change number {}\n\nBody text for change {}.\n\nRefs: ABC-{}\n\x1d\n",
+                    i,
+                    i % 40,
+                    i % 40,
+                    1 + i % 12,
+                    TYPES[i % TYPES.len()],
+                    i,
+                    i,
+                    i
+                ));
+            }
+            log
+        }
+
+        const FILTER: HistoryFilter = HistoryFilter {
+            no_merges: false,
+            include_bots: false,
+            with_files: false,
+        };
+
+        fn parse_serial(log: &str, config: &Config) -> Vec<HistoryCommit> {
+            log.split('\x1e')
+                .filter_map(|record| parse_history_record(record, config, &FILTER))
+                .collect()
+        }
+
+        fn parse_parallel(log: &str, config: &Config) -> Vec<HistoryCommit> {
+            let records: Vec<&str> = log.split('\x1e').collect();
+            map_chunks_parallel(&records, |chunk| {
+                chunk
+                    .iter()
+                    .filter_map(|record| parse_history_record(record, config, &FILTER))
+                    .collect::<Vec<_>>()
+            })
+            .into_iter()
+            .flatten()
+            .collect()
+        }
+
+        fn quality_serial(commits: &[HistoryCommit], config: &Config) -> usize {
+            let progress = Progress::new("", commits.len(), false);
+            month_quality(commits, config, &progress).len()
+        }
+
+        fn quality_parallel(commits: &[HistoryCommit], config: &Config) -> usize {
+            let progress = Progress::new("", commits.len(), false);
+            map_chunks_parallel(commits, |chunk| {
+                month_quality(chunk, config, &progress).len()
+            })
+            .into_iter()
+            .sum()
+        }
+
+        pub fn history(c: &mut Criterion) {
+            let config = Config::default();
+            for size in [10_000, 100_000] {
+                let log = synthetic_log(size);
+                let commits = parse_serial(&log, &config);
+
+                let mut parse = c.benchmark_group("parse history");
+                parse.throughput(Throughput::Elements(size as u64));
+                parse.bench_with_input(BenchmarkId::new("serial", size), &log, |b, log| {
+                    b.iter(|| parse_serial(log, &config))
+                });
+                parse.bench_with_input(BenchmarkId::new("parallel", size), &log, |b, log| {
+                    b.iter(|| parse_parallel(log, &config))
+                });
+                parse.finish();
+
+                let mut quality = c.benchmark_group("quality trends");
+                quality.throughput(Throughput::Elements(size as u64));
+                quality.sample_size(10);
+                quality.bench_with_input(
+                    BenchmarkId::new("serial", size),
+                    &commits,
+                    |b, commits| b.iter(|| quality_serial(commits, &config)),
+                );
+                quality.bench_with_input(
+                    BenchmarkId::new("parallel", size),
+                    &commits,
+                    |b, commits| b.iter(|| quality_parallel(commits, &config)),
+                );
+                quality.finish();
+            }
+        }
+    }
+}
+
+criterion_group!(benches, commitkit::bench::history);
+criterion_main!(benches);
@@ -0,0 +1,71 @@
+// Bulk validation throughput: a fresh validation per message against one
+// Validator reused for the whole stream, as lint and validate --range do.
+
+use commitkit_core::{Config, GitAccess, Validator};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+// The comment char is all the validator asks git for.
+struct NoGit;
+
+impl GitAccess for NoGit {
+    fn commit_messages(&self, _: &[&str]) -> Result<Vec<(String, String)>, String> {
+        Ok(Vec::new())
+    }
+}
+
+fn messages(count: usize) -> Vec<String> {
+    (0..count)
+        .map(|i| match i % 4 {
+            0 => format!("feat(api): add endpoint {}\n\nRefs: ABC-{}\n", i, i),
+            1 => format!("fix: handle empty token {}\n\nThe parser crashed.\n", i),
+            2 => format!("Update things {}\n", i),
+            _ => format!("docs(readme): mention option {} ABC-{}\n", i, i),
+        })
+        .collect()
+}
+
+fn validation(c: &mut Criterion) {
+    let config: Config = toml::from_str(
+        r#"
+        scopes = ["api", "readme"]
+        ticket_pattern = "[A-Z]+-[0-9]+"
+        secret_patterns = [{ name = "internal host", pattern = "\\.corp\\.example\\.com" }]
+        "#,
+    )
+    .expect("valid bench config");
+    let validator = || Validator::new(&config, &NoGit).expect("valid bench patterns");
+    let mut group = c.benchmark_group("validate messages");
+    for count in [1_000, 10_000] {
+        let messages = messages(count);
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(
+            BenchmarkId::new("fresh validator", count),
+            &messages,
+            |b, messages| {
+                b.iter(|| {
+                    messages
+                        .iter()
+                        .map(|message| validator().validate(message).len())
+                        .sum::<usize>()
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("reused validator", count),
+            &messages,
+            |b, messages| {
+                b.iter(|| {
+                    let validator = validator();
+                    messages
+                        .iter()
+                        .map(|message| validator.validate(message).len())
+                        .sum::<usize>()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, validation);
+criterion_main!(benches);
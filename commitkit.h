@@ -0,0 +1,49 @@
+#ifndef COMMITKIT_H
+#define COMMITKIT_H
+
+/* Generated by cbindgen from commitkit_core.rs with the `ffi` feature; do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Checks `message` against the rules of `config`, the text of a
+// `.commitkit.toml` (empty for the defaults). Returns
+// `{"violations": [...]}` or `{"error": ...}` as JSON.
+//
+// # Safety
+//
+// Both arguments must be NUL-terminated strings. Release the result
+// with `commitkit_string_free`.
+char *commitkit_validate(const char *config, const char *message);
+
+// Splits `message` into its type, scope, subject, body, breaking flag
+// and footers, returned as JSON, or `{"error": ...}`.
+//
+// # Safety
+//
+// `message` must be a NUL-terminated string. Release the result with
+// `commitkit_string_free`.
+char *commitkit_parse(const char *message);
+
+// Builds a message from a JSON payload with `type`, `subject` and
+// optionally `scope`, `body`, `breaking` and `footers`, and checks it
+// against `config`. Returns `{"message": ..., "violations": [...]}` or
+// `{"error": ...}`.
+//
+// # Safety
+//
+// Both arguments must be NUL-terminated strings. Release the result
+// with `commitkit_string_free`.
+char *commitkit_build(const char *config, const char *payload);
+
+// Releases a string returned by the other functions.
+//
+// # Safety
+//
+// `value` must come from this library and not have been freed yet.
+// NULL is ignored.
+void commitkit_string_free(char *value);
+
+#endif  /* COMMITKIT_H */
@@ -0,0 +1,11 @@
+// Any trailer parse_trailer accepts reads back the same from its Display form.
+#![no_main]
+
+use commitkit_core::parse_trailer;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|line: &str| {
+    if let Some(trailer) = parse_trailer(line) {
+        assert_eq!(parse_trailer(&trailer.to_string()), Some(trailer));
+    }
+});
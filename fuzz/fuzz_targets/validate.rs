@@ -0,0 +1,38 @@
+// Hooks parse message files anyone can write, so neither the parser nor the
+// validator may panic on any input, and every span must index the message.
+#![no_main]
+
+use std::sync::OnceLock;
+
+use commitkit_core::{CommitMessage, Config, GitAccess, Validator};
+use libfuzzer_sys::fuzz_target;
+
+struct NoGit;
+
+impl GitAccess for NoGit {
+    fn commit_messages(&self, _: &[&str]) -> Result<Vec<(String, String)>, String> {
+        Ok(Vec::new())
+    }
+}
+
+fn validator() -> &'static Validator<'static> {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+    static VALIDATOR: OnceLock<Validator> = OnceLock::new();
+    VALIDATOR.get_or_init(|| {
+        let config = CONFIG.get_or_init(|| Config {
+            ticket_pattern: Some(String::from("[A-Z]+-[0-9]+")),
+            require_sign_off: true,
+            ..Config::default()
+        });
+        Validator::new(config, &NoGit).expect("valid patterns")
+    })
+}
+
+fuzz_target!(|message: &str| {
+    for violation in validator().validate(message) {
+        assert!(message.get(violation.span).is_some());
+    }
+    if let Some(commit) = CommitMessage::parse(message, '#') {
+        let _ = commit.to_string();
+    }
+});
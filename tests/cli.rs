@@ -0,0 +1,382 @@
+// End-to-end tests driving the built binary in throwaway repositories.
+
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{self, Stdio};
+
+use assert_cmd::assert::Assert;
+use assert_cmd::cargo::cargo_bin;
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+struct TempRepo {
+    root: TempDir,
+}
+
+impl TempRepo {
+    // An empty repository with its own HOME, so no user config or global
+    // git settings leak into the test.
+    fn new() -> TempRepo {
+        let root = tempfile::Builder::new()
+            .prefix("commitkit-cli-")
+            .tempdir()
+            .unwrap();
+        fs::create_dir(root.path().join("home")).unwrap();
+        fs::create_dir(root.path().join("repo")).unwrap();
+        let repo = TempRepo { root };
+        repo.git(&["init", "--quiet"]).success();
+        repo.git(&["config", "user.name", "Test"]).success();
+        repo.git(&["config", "user.email", "test@example.com"])
+            .success();
+        repo
+    }
+
+    fn dir(&self) -> PathBuf {
+        self.root.path().join("repo")
+    }
+
+    // The binary's directory goes first on PATH so installed hooks find it.
+    fn command(&self, program: impl AsRef<OsStr>) -> process::Command {
+        let binary = cargo_bin("commitkit");
+        let path = env::join_paths(
+            std::iter::once(binary.parent().unwrap().to_path_buf())
+                .chain(env::split_paths(&env::var_os("PATH").unwrap_or_default())),
+        )
+        .unwrap();
+        let mut command = process::Command::new(program);
+        command
+            .current_dir(self.dir())
+            .env("HOME", self.root.path().join("home"))
+            .env("PATH", path)
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .env_remove("COMMITKIT_SKIP")
+            .env_remove("GIT_DIR")
+            .stdin(Stdio::null());
+        command
+    }
+
+    fn git(&self, args: &[&str]) -> Assert {
+        Command::from_std(self.command("git")).args(args).assert()
+    }
+
+    fn commitkit(&self) -> Command {
+        Command::from_std(self.command(cargo_bin("commitkit")))
+    }
+
+    fn write(&self, path: &str, content: &str) {
+        fs::write(self.dir().join(path), content).unwrap();
+    }
+
+    fn read(&self, path: &str) -> String {
+        fs::read_to_string(self.dir().join(path)).unwrap()
+    }
+
+    fn stage(&self, path: &str, content: &str) {
+        self.write(path, content);
+        self.git(&["add", path]).success();
+    }
+
+    fn last_message(&self) -> String {
+        let log = self.git(&["log", "-1", "--format=%B"]).success();
+        String::from_utf8(log.get_output().stdout.clone()).unwrap()
+    }
+}
+
+#[test]
+fn flags_create_a_commit_without_prompts() {
+    let repo = TempRepo::new();
+    repo.stage("a.txt", "a\n");
+    repo.commitkit()
+        .args(["--type", "feat", "--scope", "core", "--subject", "add a"])
+        .assert()
+        .success();
+    assert_eq!(repo.last_message().trim(), "feat(core): add a");
+}
+
+#[test]
+fn invalid_flag_messages_are_not_committed() {
+    let repo = TempRepo::new();
+    repo.stage("a.txt", "a\n");
+    repo.commitkit()
+        .args(["--type", "nope", "--subject", "add a"])
+        .assert()
+        .failure();
+    repo.git(&["rev-parse", "--verify", "HEAD"]).failure();
+}
+
+#[test]
+fn validate_exit_codes() {
+    let repo = TempRepo::new();
+    repo.commitkit()
+        .args(["validate", "-"])
+        .write_stdin("fix: handle empty token\n")
+        .assert()
+        .code(0);
+    repo.commitkit()
+        .args(["validate", "-"])
+        .write_stdin("handle empty token\n")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("[header-format]"));
+    repo.commitkit()
+        .args(["validate", "--bogus"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn installed_commit_msg_hook_rejects_bad_messages() {
+    let repo = TempRepo::new();
+    repo.commitkit()
+        .args(["hooks", "install"])
+        .assert()
+        .success();
+    assert!(predicate::path::exists().eval(&repo.dir().join(".git/hooks/commit-msg")));
+
+    repo.stage("a.txt", "a\n");
+    repo.git(&["commit", "-m", "bad message"]).failure();
+    repo.git(&["commit", "-m", "fix: good message"]).success();
+    assert_eq!(repo.last_message().trim(), "fix: good message");
+
+    repo.stage("b.txt", "b\n");
+    Command::from_std(repo.command("git"))
+        .args(["commit", "-m", "anything goes"])
+        .env("COMMITKIT_SKIP", "1")
+        .assert()
+        .success();
+}
+
+#[test]
+fn commits_are_recorded_in_the_changelog() {
+    let repo = TempRepo::new();
+    repo.write(".commitkit.toml", "update_changelog = true\n");
+    repo.stage("a.txt", "a\n");
+    repo.commitkit()
+        .args(["--type", "feat", "--scope", "core", "--subject", "add a"])
+        .assert()
+        .success();
+    repo.stage("b.txt", "b\n");
+    repo.commitkit()
+        .args(["--type", "fix", "--subject", "handle b"])
+        .assert()
+        .success();
+
+    let changelog = repo.read("CHANGELOG.md");
+    let added = changelog.find("### Added").unwrap();
+    let fixed = changelog.find("### Fixed").unwrap();
+    assert!(changelog.contains("## [Unreleased]"));
+    assert!(changelog[added..].contains("- **core:** add a ("));
+    assert!(changelog[fixed..].contains("- handle b ("));
+}
+
+#[test]
+fn fix_message_rewrites_the_file() {
+    let repo = TempRepo::new();
+    repo.write("MSG", "Feat: add a.\n");
+    repo.commitkit()
+        .args(["fix-message", "MSG"])
+        .assert()
+        .success();
+    assert_eq!(repo.read("MSG"), "feat: add a\n");
+}
+
+#[test]
+fn config_edit_keeps_comments() {
+    let repo = TempRepo::new();
+    repo.write(
+        ".commitkit.toml",
+        "# Team settings\nmax_subject_len = 60 # agreed in review\n\n# Areas of the code\nscopes = [\"core\"]\n",
+    );
+    repo.commitkit()
+        .args(["config", "edit"])
+        .write_stdin("3\napi\n7\n72\ns\n")
+        .assert()
+        .success();
+    assert_eq!(
+        repo.read(".commitkit.toml"),
+        "# Team settings\nmax_subject_len = 72 # agreed in review\n\n# Areas of the code\nscopes = [\"core\", \"api\"]\n"
+    );
+}
+
+#[test]
+fn template_dir_comes_from_the_environment() {
+    let repo = TempRepo::new();
+    repo.write(".commitkit.toml", "templates_dir = \"configured\"\n");
+    fs::create_dir_all(repo.dir().join("configured")).unwrap();
+    fs::create_dir_all(repo.dir().join("shared")).unwrap();
+    let template = "description = \"From {}\"\nmessage = \"chore: tidy\"\n";
+    repo.write("configured/tidy.toml", &template.replace("{}", "config"));
+    repo.write("shared/tidy.toml", &template.replace("{}", "env"));
+
+    repo.commitkit()
+        .args(["template", "list"])
+        .assert()
+        .stdout(predicate::str::contains("From config"));
+    repo.commitkit()
+        .args(["template", "list"])
+        .env("COMMITKIT_TEMPLATE_DIR", "shared")
+        .assert()
+        .stdout(
+            predicate::str::contains("From env").and(predicate::str::contains("From config").not()),
+        );
+}
+
+#[test]
+fn lsp_diagnostics_skip_lines_with_core_comment_char() {
+    let repo = TempRepo::new();
+    repo.git(&["config", "core.commentChar", ";"]).success();
+    let frame = |body: &str| format!("Content-Length: {}\r\n\r\n{}", body.len(), body);
+    let input = [
+        r#"{"jsonrpc":"2.0","method":"textDocument/didOpen","params":{"textDocument":{"uri":"file:///MSG","text":"fix: add a\n; Edit the message\n"}}}"#,
+        r#"{"jsonrpc":"2.0","method":"exit"}"#,
+    ]
+    .map(frame)
+    .concat();
+    repo.commitkit()
+        .arg("lsp")
+        .write_stdin(input)
+        .assert()
+        .stdout(predicate::str::contains(r#""diagnostics":[]"#));
+}
+
+#[test]
+fn setup_takes_defaults_or_stops_on_closed_stdin() {
+    let repo = TempRepo::new();
+    let config = repo.dir().join(".commitkit.toml");
+    repo.commitkit().arg("setup").assert().code(130);
+    assert!(predicate::path::missing().eval(&config));
+
+    repo.commitkit().args(["setup", "--yes"]).assert().success();
+    assert!(predicate::path::exists().eval(&config));
+    assert!(predicate::path::exists().eval(&repo.dir().join(".git/hooks/commit-msg")));
+    assert!(predicate::path::missing().eval(&repo.dir().join("CHANGELOG.md")));
+}
+
+#[test]
+fn config_edit_stops_on_closed_stdin() {
+    let repo = TempRepo::new();
+    repo.write(".commitkit.toml", "scopes = [\"core\"]\n");
+    repo.commitkit()
+        .args(["config", "edit"])
+        .write_stdin("3\n")
+        .assert()
+        .code(1);
+    assert_eq!(repo.read(".commitkit.toml"), "scopes = [\"core\"]\n");
+}
+
+#[test]
+fn stats_follow_mailmap_changes() {
+    let repo = TempRepo::new();
+    repo.stage("a.txt", "a\n");
+    repo.git(&["commit", "--quiet", "-m", "fix: handle a"])
+        .success();
+    repo.commitkit()
+        .arg("stats")
+        .assert()
+        .stdout(predicate::str::contains("1  Test"));
+
+    repo.write(".mailmap", "Real Name <test@example.com>\n");
+    repo.commitkit()
+        .arg("stats")
+        .assert()
+        .stdout(predicate::str::contains("1  Real Name"));
+}
+
+// Ctrl+D finishes the body; with a prompt timeout the footer prompt after it
+// must still read from the terminal.
+#[cfg(unix)]
+#[test]
+fn body_finished_with_eof_keeps_later_prompts_alive() {
+    let repo = TempRepo::new();
+    repo.write(
+        ".commitkit.toml",
+        "prompt_flow = [\"subject\", \"body\", \"footer\"]\nprompt_timeout = 30\n",
+    );
+    repo.stage("a.txt", "a\n");
+    let mut command = repo.command(cargo_bin("commitkit"));
+    command.args(["--type", "fix"]).stdin(Stdio::inherit());
+    let mut session = rexpect::session::spawn_command(command, Some(10_000)).unwrap();
+    session.exp_string("subject").unwrap();
+    session.send_line("handle empty token").unwrap();
+    session.exp_string("body").unwrap();
+    session.send_line("Body line").unwrap();
+    session.send_control('d').unwrap();
+    session.exp_string("footer").unwrap();
+    session.send_line("Refs: #12").unwrap();
+    session.exp_eof().unwrap();
+    assert_eq!(
+        repo.last_message().trim(),
+        "fix: handle empty token\n\nBody line\n\nRefs: #12"
+    );
+}
+
+#[test]
+fn extends_sources_cannot_pass_git_options() {
+    let repo = TempRepo::new();
+    repo.write(
+        ".commitkit.toml",
+        "extends = \"--upload-pack=touch pwned\"\n",
+    );
+    repo.commitkit()
+        .args(["validate", "-"])
+        .write_stdin("fix: handle a\n")
+        .assert()
+        .code(2);
+    assert!(predicate::path::missing().eval(&repo.dir().join("pwned")));
+}
+
+#[cfg(unix)]
+#[test]
+fn never_offering_setup_keeps_the_home_config_intact() {
+    let repo = TempRepo::new();
+    let home_config = repo.root.path().join("home/.commitkit.toml");
+    fs::write(
+        &home_config,
+        "# My settings\nsign_off_commits = true # always\n\n[theme]\naccent = \"green\"\n",
+    )
+    .unwrap();
+    let mut command = repo.command(cargo_bin("commitkit"));
+    command.stdin(Stdio::inherit());
+    let mut session = rexpect::session::spawn_command(command, Some(10_000)).unwrap();
+    session.exp_string("[Y/n/never]").unwrap();
+    session.send_line("never").unwrap();
+    session.exp_string("will not offer").unwrap();
+    session.send_control('c').unwrap();
+    session.exp_eof().unwrap();
+    assert_eq!(
+        fs::read_to_string(&home_config).unwrap(),
+        "# My settings\nsign_off_commits = true # always\nsetup_prompt = false\n\n[theme]\naccent = \"green\"\n"
+    );
+}
+
+#[test]
+fn pre_push_hook_lints_the_pushed_refs() {
+    let repo = TempRepo::new();
+    let remote = repo.root.path().join("remote.git");
+    let remote = remote.to_str().unwrap();
+    repo.git(&["init", "--quiet", "--bare", remote]).success();
+    repo.git(&["remote", "add", "origin", remote]).success();
+    repo.stage("a.txt", "a\n");
+    repo.git(&["commit", "-m", "feat: first"]).success();
+    repo.git(&["branch", "-M", "main"]).success();
+    repo.commitkit()
+        .args(["hooks", "install", "pre-push"])
+        .assert()
+        .success();
+    repo.git(&["push", "origin", "main"]).success();
+
+    // The bad commit is on a branch that is not checked out.
+    repo.git(&["checkout", "--quiet", "-b", "topic"]).success();
+    repo.stage("b.txt", "b\n");
+    repo.git(&["commit", "-m", "bad message"]).success();
+    repo.git(&["checkout", "--quiet", "main"]).success();
+    repo.git(&["push", "origin", "topic"]).failure();
+
+    repo.stage("c.txt", "c\n");
+    repo.git(&["commit", "-m", "fix: second"]).success();
+    repo.git(&["push", "origin", "main"]).success();
+    repo.git(&["push", "origin", "main", "topic"]).failure();
+}